@@ -1,34 +1,125 @@
 use anyhow::{Context, Result};
-use indicatif::{ProgressBar, ProgressStyle};
+use clap::Parser;
+use crc32fast::Hasher;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use reqwest::blocking::Client;
 use scraper::{Html, Selector};
 use sevenz_rust::SevenZReader;
+use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{BufReader, Read, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use zip::ZipArchive;
 
+/// Maximum number of attempts for a single file's download before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// Downloads ROMs from vimm.net vault pages listed in a links file.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    /// Directory downloaded files are saved to, relative to the current directory
+    #[arg(long, default_value = "downloads")]
+    output_dir: String,
+
+    /// Path to the file listing vault URLs to download, one per line
+    #[arg(long, default_value = "links.txt")]
+    links_file: String,
+
+    /// Keep files that fail CRC verification instead of deleting them
+    #[arg(long)]
+    test: bool,
+
+    /// Process the links file once instead of polling it forever
+    #[arg(long)]
+    once: bool,
+
+    /// Decompress archives and hash the payload instead of trusting the stored CRC header
+    #[arg(long)]
+    verify_deep: bool,
+
+    /// Number of URLs to download concurrently
+    #[arg(long, default_value_t = 3)]
+    workers: usize,
+}
+
+/// Per-run settings threaded through the download pipeline, grouped so that
+/// `process_url`/`download_and_verify` take one reference instead of a growing
+/// list of positional arguments.
+struct DownloadConfig<'a> {
+    downloads_dir: &'a str,
+    test_mode: bool,
+    verify_deep: bool,
+    multi_progress: &'a MultiProgress,
+    in_flight: &'a Mutex<HashSet<String>>,
+}
+
+/// Releases a filename claimed via `DownloadConfig::in_flight` when dropped, so a
+/// download that errors out or panics doesn't permanently block that file.
+struct InFlightGuard<'a> {
+    in_flight: &'a Mutex<HashSet<String>>,
+    filename: String,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.in_flight.lock().unwrap().remove(&self.filename);
+    }
+}
+
+/// Wraps a `ProgressBar` so a download that errors out mid-transfer still has its
+/// bar finished: it is abandoned at its last position and stays drawn on the shared
+/// `MultiProgress` (it is not removed from it).
+struct ProgressGuard(ProgressBar);
+
+impl std::ops::Deref for ProgressGuard {
+    type Target = ProgressBar;
+
+    fn deref(&self) -> &ProgressBar {
+        &self.0
+    }
+}
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        if !self.0.is_finished() {
+            self.0.abandon_with_message("Download failed");
+        }
+    }
+}
+
 fn main() -> Result<()> {
-    // Check for --test flag
-    let test_mode = false;
-    if test_mode {
+    let cli = Cli::parse();
+
+    if cli.test {
         println!("*** TEST MODE: Files will NOT be deleted on CRC mismatch ***");
     }
 
-    // Create downloads folder if it doesn't exist
-    let downloads_dir = "downloads";
-    fs::create_dir_all(downloads_dir)?;
+    if cli.verify_deep {
+        println!("*** DEEP VERIFY MODE: decompressing archives to hash their payload ***");
+    }
+
+    // Create the downloads folder if it doesn't exist
+    let downloads_dir = std::env::current_dir()
+        .context("Failed to read current directory")?
+        .join(&cli.output_dir);
+    fs::create_dir_all(&downloads_dir)?;
+    let downloads_dir = downloads_dir
+        .to_str()
+        .context("Output directory path is not valid UTF-8")?;
 
     let client = Client::builder()
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/144.0.0.0 Safari/537.36")
         .build()?;
 
     loop {
-        // Read links.txt file
-        let links_content = match fs::read_to_string("links.txt") {
+        // Read the links file
+        let links_content = match fs::read_to_string(&cli.links_file) {
             Ok(content) => content,
             Err(e) => {
-                eprintln!("Error reading links.txt: {}. Waiting...", e);
+                eprintln!("Error reading {}: {}. Waiting...", cli.links_file, e);
                 std::thread::sleep(std::time::Duration::from_secs(5));
                 continue;
             }
@@ -42,42 +133,80 @@ fn main() -> Result<()> {
             .collect();
 
         if urls.is_empty() {
-            println!("No URLs found in links.txt. Waiting...");
+            if cli.once {
+                println!("No URLs found in {}.", cli.links_file);
+                break;
+            }
+            println!("No URLs found in {}. Waiting...", cli.links_file);
             std::thread::sleep(std::time::Duration::from_secs(5));
             continue;
         }
 
-        let mut downloaded_any = false;
-        for url in urls {
-            if !url.contains("vimm.net/vault/") {
-                println!("Skipping invalid URL: {}", url);
-                continue;
-            }
+        let jobs = Mutex::new(urls);
+        let multi_progress = MultiProgress::new();
+        let downloaded_any = AtomicBool::new(false);
+        let in_flight = Mutex::new(HashSet::new());
+
+        let config = DownloadConfig {
+            downloads_dir,
+            test_mode: cli.test,
+            verify_deep: cli.verify_deep,
+            multi_progress: &multi_progress,
+            in_flight: &in_flight,
+        };
 
-            match process_url(&client, &url, downloads_dir, test_mode) {
-                Ok(true) => {
-                    downloaded_any = true;
-                    println!("Successfully downloaded from: {}", url);
-                    println!()
-                }
-                Ok(false) => {
-                    println!("File already exists for: {}", url);
-                    println!()
-                }
-                Err(e) => {
-                    eprintln!("Error processing {}: {}", url, e);
-                }
+        std::thread::scope(|scope| {
+            for _ in 0..cli.workers {
+                let jobs = &jobs;
+                let client = &client;
+                let config = &config;
+                let downloaded_any = &downloaded_any;
+
+                scope.spawn(move || loop {
+                    let url = match jobs.lock().unwrap().pop() {
+                        Some(url) => url,
+                        None => break,
+                    };
+
+                    if !url.contains("vimm.net/vault/") {
+                        println!("Skipping invalid URL: {}", url);
+                        continue;
+                    }
+
+                    match process_url(client, &url, config) {
+                        Ok(true) => {
+                            downloaded_any.store(true, Ordering::Relaxed);
+                            println!("Successfully downloaded from: {}", url);
+                            println!()
+                        }
+                        Ok(false) => {
+                            println!("File already exists for: {}", url);
+                            println!()
+                        }
+                        Err(e) => {
+                            eprintln!("Error processing {}: {}", url, e);
+                        }
+                    }
+                });
             }
-        }
+        });
 
-        if !downloaded_any {
+        if !downloaded_any.load(Ordering::Relaxed) {
+            if cli.once {
+                println!("All files already downloaded.");
+                break;
+            }
             println!("All files already downloaded. Waiting for new links...");
             std::thread::sleep(std::time::Duration::from_secs(5));
+        } else if cli.once {
+            break;
         }
     }
+
+    Ok(())
 }
 
-fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool) -> Result<bool> {
+fn process_url(client: &Client, url: &str, config: &DownloadConfig) -> Result<bool> {
     println!("Processing: {}", url);
 
     // Fetch the vault page
@@ -116,15 +245,57 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
     let vault_id = url.split('/').last().unwrap_or("unknown");
     let filename = sanitize_filename(&title, vault_id);
 
-    // Download the file
-    println!("Initiating download...");
-
     // Submit GET request with mediaId as query parameter
     let download_url_with_params = format!("{}?mediaId={}", download_url, media_id);
     println!("Final download URL: {}", download_url_with_params);
 
-    let mut response = client
-        .get(&download_url_with_params)
+    // Download and verify, retrying transient failures with exponential backoff so a
+    // dropped connection or a truncated file doesn't abort the whole URL.
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match download_and_verify(
+            client,
+            &download_url_with_params,
+            &filename,
+            &expected_crc,
+            config,
+        ) {
+            Ok(downloaded) => return Ok(downloaded),
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS && is_transient_error(&e) => {
+                let delay_secs = 1u64 << (attempt - 1).min(5);
+                let delay_secs = delay_secs.min(30);
+                eprintln!(
+                    "Attempt {}/{} failed ({}), retrying in {}s...",
+                    attempt, MAX_DOWNLOAD_ATTEMPTS, e, delay_secs
+                );
+                std::thread::sleep(std::time::Duration::from_secs(delay_secs));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Fetches, downloads (resuming any `.pending` data) and CRC-verifies a single file.
+/// Returns `Ok(true)` if a new download happened, `Ok(false)` if the file already
+/// existed and verified cleanly.
+fn download_and_verify(
+    client: &Client,
+    download_url_with_params: &str,
+    filename: &str,
+    expected_crc: &str,
+    config: &DownloadConfig,
+) -> Result<bool> {
+    // Download the file
+    println!("Initiating download...");
+
+    // Learn the real filename via a lightweight HEAD request first. A full GET here
+    // would either have its body read (defeating resume, since we don't yet know
+    // whether a `.pending` file needs a Range request) or be thrown away unread
+    // (wasting a full duplicate request/connection) - a HEAD gets us the headers
+    // without opening a body stream at all.
+    let head_response = client
+        .head(download_url_with_params)
         .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8")
         .header("Accept-Encoding", "gzip, deflate, br, zstd")
         .header("Accept-Language", "en-US,en;q=0.9")
@@ -142,47 +313,61 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
         .header("sec-ch-ua-mobile", "?0")
         .header("sec-ch-ua-platform", "\"Windows\"")
         .send()
-        .context("Failed to download file")?;
+        .context("Failed to fetch download headers")?;
 
-    let status = response.status();
+    let status = head_response.status();
     if !status.is_success() {
-        let response_text = response
-            .text()
-            .unwrap_or_else(|_| "Failed to read response".to_string())
-            .clone();
-        println!("Response body: {}", response_text);
-        anyhow::bail!("Download failed with status: {}", status);
+        anyhow::bail!("Download headers request failed with status: {}", status);
     }
 
     // Extract filename from content-disposition header
-    let actual_filename = if let Some(content_disp) = response.headers().get("content-disposition")
-    {
-        if let Ok(disp_str) = content_disp.to_str() {
-            // Parse: attachment; filename="Army Men - Air Attack 2 (USA).7z"
-            if let Some(filename_part) = disp_str.split("filename=").nth(1) {
-                filename_part.trim_matches('"').to_string()
+    let actual_filename =
+        if let Some(content_disp) = head_response.headers().get("content-disposition") {
+            if let Ok(disp_str) = content_disp.to_str() {
+                // Parse: attachment; filename="Army Men - Air Attack 2 (USA).7z"
+                if let Some(filename_part) = disp_str.split("filename=").nth(1) {
+                    filename_part.trim_matches('"').to_string()
+                } else {
+                    filename.to_string()
+                }
             } else {
-                filename.clone()
+                filename.to_string()
             }
         } else {
-            filename.clone()
-        }
-    } else {
-        filename.clone()
-    };
+            filename.to_string()
+        };
 
     println!("Actual filename: {}", actual_filename);
 
-    // Update paths with actual filename
-    let final_path = Path::new(downloads_dir).join(&actual_filename);
-    let pending_path = Path::new(downloads_dir).join(format!("{}.pending", actual_filename));
+    // Claim this filename before touching its files on disk. Two workers downloading
+    // the same vault URL twice, or different URLs that resolve to the same filename,
+    // would otherwise race on the same `.pending`/final path and corrupt the download
+    // or each other's CRC check.
+    while !config
+        .in_flight
+        .lock()
+        .unwrap()
+        .insert(actual_filename.clone())
+    {
+        println!(
+            "Another worker is already handling {}, waiting...",
+            actual_filename
+        );
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    let _in_flight_guard = InFlightGuard {
+        in_flight: config.in_flight,
+        filename: actual_filename.clone(),
+    };
 
-    let total_size = response.content_length().unwrap_or(0);
+    // Update paths with actual filename
+    let final_path = Path::new(config.downloads_dir).join(&actual_filename);
+    let pending_path = Path::new(config.downloads_dir).join(format!("{}.pending", actual_filename));
 
     // Check if file already exists and verify CRC from archive metadata
     if final_path.exists() {
         println!("Verifying existing file...");
-        match get_crc_from_archive(&final_path) {
+        match verify_archive(&final_path, expected_crc, config.verify_deep, Some(&actual_filename)) {
             Ok(archive_crc) => {
                 if archive_crc == expected_crc {
                     println!(
@@ -197,7 +382,7 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
                         "File exists but CRC32 mismatch. Expected: {}, Got: {}",
                         expected_crc, archive_crc
                     );
-                    if test_mode {
+                    if config.test_mode {
                         panic!("TEST MODE: CRC mismatch on existing file - not deleting");
                     }
                     println!("Re-downloading...");
@@ -206,7 +391,7 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
             }
             Err(e) => {
                 println!("Error reading archive CRC: {}", e);
-                if test_mode {
+                if config.test_mode {
                     panic!("TEST MODE: Failed to read CRC from existing file - not deleting");
                 }
                 println!("Re-downloading...");
@@ -217,20 +402,65 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
 
     println!("Downloading to: {}", pending_path.display());
 
-    // Write to pending file
-    let mut file = File::create(&pending_path)?;
+    // Resume from a previous attempt if a .pending file is already sitting there.
+    let existing_len = fs::metadata(&pending_path).map(|m| m.len()).unwrap_or(0);
+
+    if existing_len > 0 {
+        println!(
+            "Found existing .pending file ({} bytes), attempting to resume...",
+            existing_len
+        );
+    }
+
+    // Issue exactly one real download request - ranged when resuming, plain otherwise -
+    // and reuse its body for the transfer below instead of opening a second connection.
+    let mut request = client.get(download_url_with_params);
+    if existing_len > 0 {
+        request = request.header("Range", format!("bytes={}-", existing_len));
+    }
+    let mut response = request.send().context("Failed to start download")?;
+
+    let (mut downloaded, resuming) = match response.status() {
+        reqwest::StatusCode::PARTIAL_CONTENT => (existing_len, true),
+        reqwest::StatusCode::RANGE_NOT_SATISFIABLE => {
+            // Only the 416 case genuinely needs a second request: there is no usable
+            // body to fall back to, so we have to ask again without the Range header.
+            println!("Server rejected our range, restarting from scratch...");
+            response = client
+                .get(download_url_with_params)
+                .send()
+                .context("Failed to restart download")?;
+            (0, false)
+        }
+        _ if existing_len > 0 => {
+            println!("Server does not support resuming, restarting from scratch...");
+            (0, false)
+        }
+        _ => (0, false),
+    };
+
+    let total_size = downloaded + response.content_length().unwrap_or(0);
+
+    // Write to pending file, appending if we're resuming, truncating otherwise
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(resuming)
+        .write(true)
+        .truncate(!resuming)
+        .open(&pending_path)?;
 
-    // Setup progress bar
-    let pb = ProgressBar::new(total_size);
+    // Setup progress bar, attached to the shared multi-progress so concurrent
+    // downloads each get their own stacked bar instead of clobbering the console.
+    let pb = ProgressGuard(config.multi_progress.add(ProgressBar::new(total_size)));
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
             .unwrap()
             .progress_chars("#>-"),
     );
+    pb.set_position(downloaded);
 
     // Download with progress
-    let mut downloaded: u64 = 0;
     let mut buffer = [0; 8192];
     loop {
         let n = response.read(&mut buffer)?;
@@ -245,13 +475,21 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
     pb.finish_with_message("Download completed");
     println!();
 
+    if downloaded != total_size {
+        anyhow::bail!(
+            "Download incomplete: expected {} bytes, got {}",
+            total_size,
+            downloaded
+        );
+    }
+
     // Rename to final filename
     fs::rename(&pending_path, &final_path).context("Failed to rename file from .pending")?;
 
     // Verify download by reading CRC from archive metadata
     println!("Verifying download...");
-    let archive_crc =
-        get_crc_from_archive(&final_path).context("Failed to read CRC from archive")?;
+    let archive_crc = verify_archive(&final_path, expected_crc, config.verify_deep, Some(&actual_filename))
+        .context("Failed to verify downloaded archive")?;
 
     if archive_crc == expected_crc {
         println!(
@@ -262,7 +500,7 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
         println!("✗ CRC32 verification FAILED!");
         println!("  Expected: {}", expected_crc);
         println!("  Got:      {}", archive_crc);
-        if test_mode {
+        if config.test_mode {
             panic!("TEST MODE: CRC mismatch on downloaded file - not deleting");
         }
         fs::remove_file(&final_path)?;
@@ -273,6 +511,56 @@ fn process_url(client: &Client, url: &str, downloads_dir: &str, test_mode: bool)
     Ok(true)
 }
 
+/// Decides whether a failure from `download_and_verify` is worth retrying:
+/// connection resets/timeouts, server-side 5xx responses, and CRC mismatches
+/// (a stored-CRC mismatch has already removed the bad file, and a failed deep
+/// verification leaves it for the next attempt's existing-file check to remove,
+/// so a retry starts clean).
+fn is_transient_error(err: &anyhow::Error) -> bool {
+    fn is_transient_reqwest_error(err: &reqwest::Error) -> bool {
+        err.is_timeout() || err.is_connect() || err.is_body()
+    }
+
+    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+        return is_transient_reqwest_error(reqwest_err);
+    }
+
+    // Mid-stream failures from `response.read()` surface as `std::io::Error`, not
+    // `reqwest::Error`: reqwest's blocking `Read` impl wraps its error via
+    // `io::Error::new(ErrorKind::Other, reqwest_error)`, and that wrapping isn't
+    // visible through `source()` (only through `get_ref()`), so it has to be
+    // unwrapped explicitly here rather than relying on `downcast_ref`/`.chain()`.
+    if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+        use std::io::ErrorKind;
+        if matches!(
+            io_err.kind(),
+            ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::TimedOut
+                | ErrorKind::UnexpectedEof
+                | ErrorKind::Interrupted
+                | ErrorKind::BrokenPipe
+        ) {
+            return true;
+        }
+
+        if let Some(reqwest_err) = io_err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<reqwest::Error>())
+        {
+            return is_transient_reqwest_error(reqwest_err);
+        }
+    }
+
+    let message = err.to_string();
+    message.contains("status: 5")
+        || message.contains("CRC does not match")
+        || message.contains("Download incomplete")
+        || err
+            .chain()
+            .any(|cause| cause.to_string().contains("Deep verification FAILED"))
+}
+
 fn sanitize_filename(title: &str, vault_id: &str) -> String {
     // Remove "Vimm's Lair -" and other prefixes
     let title = title
@@ -343,17 +631,32 @@ fn extract_hash(document: &Html, span_id: &str) -> Option<String> {
         .map(|s| s.trim().to_lowercase())
 }
 
-fn get_crc_from_archive(file_path: &Path) -> Result<String> {
-    let extension = file_path
-        .extension()
-        .and_then(|s| s.to_str())
-        .map(|s| s.to_lowercase())
-        .unwrap_or_default();
-
-    match extension.as_str() {
-        "7z" => get_crc_from_7z(file_path),
-        "zip" => get_crc_from_zip(file_path),
-        _ => anyhow::bail!("Unsupported archive format: {}", extension),
+/// Picks the format to read `file_path` as: the extension of `disposition_filename`
+/// (the `content-disposition` name) when it is `7z`, `zip` or `rar`, otherwise that
+/// of `file_path`'s own name. Returns `None` when neither names one of those formats.
+fn detect_archive_format(file_path: &Path, disposition_filename: Option<&str>) -> Option<String> {
+    let extension_of = |name: &str| {
+        Path::new(name)
+            .extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_lowercase())
+    };
+
+    disposition_filename
+        .and_then(extension_of)
+        .into_iter()
+        .chain(file_path.file_name().and_then(|s| s.to_str()).and_then(extension_of))
+        .find(|ext| matches!(ext.as_str(), "7z" | "zip" | "rar"))
+}
+
+fn get_crc_from_archive(file_path: &Path, disposition_filename: Option<&str>) -> Result<String> {
+    match detect_archive_format(file_path, disposition_filename).as_deref() {
+        Some("7z") => get_crc_from_7z(file_path),
+        Some("zip") => get_crc_from_zip(file_path),
+        Some("rar") => get_crc_from_rar(file_path),
+        // Not a recognized archive - treat it as a raw ROM dump (.bin/.iso/.nes/...)
+        // and hash the whole file instead of an archive entry.
+        _ => get_crc_from_raw_file(file_path),
     }
 }
 
@@ -393,3 +696,162 @@ fn get_crc_from_zip(file_path: &Path) -> Result<String> {
 
     anyhow::bail!("No CRC found in zip archive")
 }
+
+fn get_crc_from_rar(file_path: &Path) -> Result<String> {
+    let archive = unrar::Archive::new(file_path)
+        .open_for_listing()
+        .context("Failed to open rar archive")?;
+
+    // Get the first file's CRC from the archive
+    for entry in archive {
+        let entry = entry.context("Failed to read rar archive entry")?;
+        if !entry.is_directory() && entry.file_crc != 0 {
+            return Ok(format!("{:08x}", entry.file_crc));
+        }
+    }
+
+    anyhow::bail!("No CRC found in rar archive")
+}
+
+/// Hashes a whole file with CRC32, for ROM dumps handed back uncompressed
+/// (.bin/.iso/.nes/...) rather than packed in a recognized archive format.
+fn get_crc_from_raw_file(file_path: &Path) -> Result<String> {
+    let file = File::open(file_path)?;
+    let mut reader = BufReader::new(file);
+    let mut hasher = Hasher::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(format!("{:08x}", hasher.finalize()))
+}
+
+/// Verifies an archive against `expected_crc` using its stored CRC header, and, when
+/// `verify_deep` is set, also decompresses the first entry to hash the actual payload.
+/// A stored-header match alone doesn't catch a truncated or bit-rotted archive, since
+/// the header itself survives that kind of corruption untouched.
+fn verify_archive(
+    file_path: &Path,
+    expected_crc: &str,
+    verify_deep: bool,
+    disposition_filename: Option<&str>,
+) -> Result<String> {
+    let archive_crc = get_crc_from_archive(file_path, disposition_filename)?;
+
+    if verify_deep {
+        println!("Deep-verifying decompressed payload...");
+        let deep_crc = get_deep_crc_from_archive(file_path, disposition_filename)
+            .context("Failed to decompress archive for deep verification")?;
+        println!(
+            "Deep CRC32 (decompressed payload): {} (archive header: {}, expected: {})",
+            deep_crc, archive_crc, expected_crc
+        );
+
+        if deep_crc != expected_crc {
+            anyhow::bail!(
+                "Deep verification FAILED: decompressed payload CRC32 {} does not match expected {}",
+                deep_crc,
+                expected_crc
+            );
+        }
+    }
+
+    Ok(archive_crc)
+}
+
+fn get_deep_crc_from_archive(file_path: &Path, disposition_filename: Option<&str>) -> Result<String> {
+    match detect_archive_format(file_path, disposition_filename).as_deref() {
+        Some("7z") => get_deep_crc_from_7z(file_path),
+        Some("zip") => get_deep_crc_from_zip(file_path),
+        Some("rar") => get_deep_crc_from_rar(file_path),
+        // Not a recognized archive - it's a raw ROM dump, and the whole-file hash
+        // `get_crc_from_raw_file` already computes *is* the decompressed payload.
+        _ => get_crc_from_raw_file(file_path),
+    }
+}
+
+fn get_deep_crc_from_7z(file_path: &Path) -> Result<String> {
+    let len = fs::metadata(file_path)?.len();
+    let file = File::open(file_path)?;
+    let mut reader = SevenZReader::new(file, len, "".into()).context("Failed to open 7z archive")?;
+
+    let mut crc = None;
+    reader
+        .for_each_entries(|entry, entry_reader| {
+            if crc.is_some() || entry.is_directory() || !entry.has_stream() {
+                return Ok(true);
+            }
+
+            let mut hasher = Hasher::new();
+            let mut buffer = [0u8; 8192];
+            loop {
+                let n = entry_reader.read(&mut buffer)?;
+                if n == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..n]);
+            }
+            crc = Some(hasher.finalize());
+
+            Ok(true)
+        })
+        .context("Failed to decompress 7z entry")?;
+
+    let crc = crc.context("No file entries found in 7z archive")?;
+    Ok(format!("{:08x}", crc))
+}
+
+fn get_deep_crc_from_zip(file_path: &Path) -> Result<String> {
+    let file = File::open(file_path)?;
+    let reader = BufReader::new(file);
+    let mut archive = ZipArchive::new(reader).context("Failed to open zip archive")?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let mut hasher = Hasher::new();
+        let mut buffer = [0u8; 8192];
+        loop {
+            let n = entry.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+        }
+
+        return Ok(format!("{:08x}", hasher.finalize()));
+    }
+
+    anyhow::bail!("No file entries found in zip archive")
+}
+
+fn get_deep_crc_from_rar(file_path: &Path) -> Result<String> {
+    let mut archive = unrar::Archive::new(file_path)
+        .open_for_processing()
+        .context("Failed to open rar archive")?;
+
+    while let Some(header) = archive
+        .read_header()
+        .context("Failed to read rar archive entry")?
+    {
+        if header.entry().is_directory() {
+            archive = header.skip().context("Failed to skip rar directory entry")?;
+            continue;
+        }
+
+        let (data, _) = header.read().context("Failed to decompress rar entry")?;
+        let mut hasher = Hasher::new();
+        hasher.update(&data);
+        return Ok(format!("{:08x}", hasher.finalize()));
+    }
+
+    anyhow::bail!("No file entries found in rar archive")
+}